@@ -0,0 +1,21 @@
+//! The state machine driving the map pipeline, from loading the selected map
+//! through to spawning walls and tanks.
+use bevy::prelude::States;
+
+/// The sequential steps taken while bringing a map into play.
+///
+/// # Variants
+/// - `LoadMap`: Resolve the selected map into an asset handle.
+/// - `GenerateProcedural`: Build a `Map` procedurally before it is consumed.
+/// - `GenerateMap`: Spawn walls, tanks and (for the full map) a camera.
+/// - `Finished`: The map is fully generated and play can begin.
+/// - `NextLevel`: Tear down the current arena and advance to the next in the playlist.
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    #[default]
+    LoadMap,
+    GenerateProcedural,
+    GenerateMap,
+    Finished,
+    NextLevel,
+}
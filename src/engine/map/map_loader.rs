@@ -0,0 +1,288 @@
+//! Asset loaders that turn files under `assets/maps` into [`Map`] assets.
+//!
+//! Two formats are understood: this crate's own RON serialization via
+//! [`MapLoader`], and LDtk level exports via [`LdtkLoader`] so arenas can be
+//! authored in the LDtk editor and dropped straight into `assets/maps`.
+use std::fmt;
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::log::warn;
+use serde::Deserialize;
+
+use super::{Coord, Map};
+
+/// Loads a [`Map`] from this crate's RON serialization.
+#[derive(Default)]
+pub struct MapLoader;
+
+/// Errors raised while loading a crate-native map file.
+#[derive(Debug)]
+pub enum MapLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for MapLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapLoaderError::Io(err) => write!(f, "could not read map file: {err}"),
+            MapLoaderError::Ron(err) => write!(f, "could not parse map file: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for MapLoaderError {}
+
+impl From<std::io::Error> for MapLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        MapLoaderError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for MapLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        MapLoaderError::Ron(err)
+    }
+}
+
+impl AssetLoader for MapLoader {
+    type Asset = Map;
+    type Settings = ();
+    type Error = MapLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'a>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["map"]
+    }
+}
+
+/// Loads a [`Map`] from an LDtk level export.
+///
+/// # Fields
+/// - `collision_layer`: The identifier of the IntGrid/Tiles layer whose solid cells become walls.
+/// - `spawn_identifier`: The entity identifier whose instances become spawn points.
+pub struct LdtkLoader {
+    pub collision_layer: String,
+    pub spawn_identifier: String,
+}
+
+impl Default for LdtkLoader {
+    fn default() -> Self {
+        LdtkLoader {
+            collision_layer: "Collision".to_string(),
+            spawn_identifier: "PlayerSpawn".to_string(),
+        }
+    }
+}
+
+/// Errors raised while importing an LDtk level.
+#[derive(Debug)]
+pub enum LdtkLoaderError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingLayer(String),
+}
+
+impl fmt::Display for LdtkLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LdtkLoaderError::Io(err) => write!(f, "could not read LDtk file: {err}"),
+            LdtkLoaderError::Json(err) => write!(f, "could not parse LDtk file: {err}"),
+            LdtkLoaderError::MissingLayer(layer) => {
+                write!(f, "LDtk level has no layer named \"{layer}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LdtkLoaderError {}
+
+impl From<std::io::Error> for LdtkLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        LdtkLoaderError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for LdtkLoaderError {
+    fn from(err: serde_json::Error) -> Self {
+        LdtkLoaderError::Json(err)
+    }
+}
+
+/// The subset of an LDtk level JSON this crate cares about.
+#[derive(Deserialize)]
+struct LdtkLevel {
+    #[serde(rename = "layerInstances")]
+    layer_instances: Vec<LdtkLayer>,
+}
+
+#[derive(Deserialize)]
+struct LdtkLayer {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__cWid")]
+    c_wid: usize,
+    #[serde(rename = "__cHei")]
+    c_hei: usize,
+    #[serde(rename = "__gridSize")]
+    grid_size: i64,
+    #[serde(default, rename = "intGridCsv")]
+    int_grid_csv: Vec<i64>,
+    #[serde(default, rename = "gridTiles")]
+    grid_tiles: Vec<LdtkTile>,
+    #[serde(default, rename = "autoLayerTiles")]
+    auto_layer_tiles: Vec<LdtkTile>,
+    #[serde(default, rename = "entityInstances")]
+    entity_instances: Vec<LdtkEntity>,
+}
+
+#[derive(Deserialize)]
+struct LdtkTile {
+    /// Pixel position of the tile's top-left corner within the layer.
+    px: [i64; 2],
+}
+
+#[derive(Deserialize)]
+struct LdtkEntity {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__grid")]
+    grid: [i64; 2],
+}
+
+/// Flips an LDtk top-down grid row index to this crate's bottom-up convention.
+fn flip_y(y: usize, height: usize) -> usize {
+    height.saturating_sub(1) - y
+}
+
+/// Translates a row-major, top-down IntGrid CSV into bottom-up wall coordinates,
+/// keeping every non-zero cell.
+fn int_grid_walls(csv: &[i64], width: usize, height: usize) -> Vec<Coord> {
+    csv.iter()
+        .enumerate()
+        .filter(|(_, &cell)| cell != 0)
+        .map(|(i, _)| (i % width, flip_y(i / width, height)))
+        .collect()
+}
+
+/// Translates a tile's pixel position into a bottom-up grid coordinate.
+fn tile_coord(px: [i64; 2], grid_size: usize, height: usize) -> Coord {
+    let gx = (px[0] as usize) / grid_size;
+    let gy = (px[1] as usize) / grid_size;
+    (gx, flip_y(gy, height))
+}
+
+impl AssetLoader for LdtkLoader {
+    type Asset = Map;
+    type Settings = ();
+    type Error = LdtkLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'a>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let level: LdtkLevel = serde_json::from_slice(&bytes)?;
+
+        let collision = level.layer_instances.iter()
+            .find(|layer| layer.identifier == self.collision_layer)
+            .ok_or_else(|| LdtkLoaderError::MissingLayer(self.collision_layer.clone()))?;
+
+        let (width, height) = (collision.c_wid, collision.c_hei);
+
+        // A collision layer may be authored either as an IntGrid (solid cells in
+        // `intGridCsv`) or as a Tiles/Auto layer (painted tiles in `gridTiles`/
+        // `autoLayerTiles`); honour both so the designated layer never yields an
+        // empty arena silently.
+        let mut walls: Vec<Coord> = int_grid_walls(&collision.int_grid_csv, width, height);
+
+        let grid_size = collision.grid_size.max(1) as usize;
+        walls.extend(
+            collision.grid_tiles.iter()
+                .chain(collision.auto_layer_tiles.iter())
+                .map(|tile| tile_coord(tile.px, grid_size, height))
+        );
+
+        if walls.is_empty() {
+            warn!(
+                "LDtk layer \"{}\" produced no walls (empty intGridCsv/gridTiles/autoLayerTiles)",
+                self.collision_layer
+            );
+        }
+
+        // An IntGrid layer that also carries an auto-tileset reports the same
+        // solid cell in both `intGridCsv` and `autoLayerTiles`; collapse the
+        // duplicates so downstream colliders and tiles are spawned once.
+        walls.sort_unstable();
+        walls.dedup();
+
+        // Entities may live on a layer whose grid height differs from the
+        // collision layer, so flip each spawn's grid-Y against its own layer.
+        let spawn_points: Vec<Coord> = level.layer_instances.iter()
+            .flat_map(|layer| {
+                let layer_height = layer.c_hei;
+                layer.entity_instances.iter()
+                    .filter(|entity| entity.identifier == self.spawn_identifier)
+                    .map(move |entity| (
+                        entity.grid[0] as usize,
+                        flip_y(entity.grid[1] as usize, layer_height),
+                    ))
+            })
+            .collect();
+
+        Ok(Map {
+            dim: (width, height),
+            walls,
+            spawn_points,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ldtk"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flip_y, int_grid_walls, tile_coord};
+
+    #[test]
+    fn flip_y_inverts_rows() {
+        // Top row (0) maps to the top of a bottom-up grid and vice versa.
+        assert_eq!(flip_y(0, 4), 3);
+        assert_eq!(flip_y(3, 4), 0);
+        // A degenerate height never underflows.
+        assert_eq!(flip_y(0, 0), 0);
+    }
+
+    #[test]
+    fn int_grid_walls_flips_and_keeps_solid_cells() {
+        // 3x2 grid, row-major top-down: solid cells at (col 0,row 0) and
+        // (col 2,row 1) become bottom-up coords (0,1) and (2,0).
+        let csv = [1, 0, 0, 0, 0, 1];
+        let walls = int_grid_walls(&csv, 3, 2);
+        assert_eq!(walls, vec![(0, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn tile_coord_divides_pixels_and_flips() {
+        // A 16px grid: the tile at pixel (32, 0) sits at column 2, top row,
+        // which flips to the top of a 4-tall grid.
+        assert_eq!(tile_coord([32, 0], 16, 4), (2, 3));
+    }
+}
@@ -5,15 +5,22 @@ use std::{fs, mem, path::Path};
 use bevy::{
     app::{Plugin, Startup, Update
     }, asset::{Asset, AssetApp, AssetServer, Assets, Handle}, color::LinearRgba, log::warn, math::Vec3, prelude::{
-        in_state, AppExtStates, Camera2dBundle, Commands, Component, GlobalTransform, Image, InheritedVisibility, IntoSystemConfigs, Mesh, NextState, Res, ResMut, Resource, Transform, ViewVisibility, Visibility
-    }, reflect::Reflect, sprite::Sprite
+        in_state, AppExtStates, Camera2dBundle, Commands, Component, DespawnRecursiveExt, Entity, GlobalTransform, IntoSystemConfigs, Mesh, NextState, Query, Res, ResMut, Resource, Transform, With
+    }, reflect::Reflect
+};
+use bevy_ecs_tilemap::prelude::{
+    TileBundle, TilePos, TileStorage, TileTextureIndex, TilemapBundle, TilemapId, TilemapPlugin,
+    TilemapSize, TilemapTexture, TilemapTileSize, TilemapType,
 };
 use bevy_rapier2d::prelude::Collider;
 use gen_state::Step;
-use map_loader::MapLoader;
+use map_loader::{LdtkLoader, MapLoader};
+use rand::Rng;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
 use serde::{Deserialize, Serialize};
 
-use crate::{engine::tank::gen::{create_minimal_tank, create_tank}, player::PlayerID};
+use crate::{engine::tank::{gen::{create_minimal_tank, create_tank}, Tank}, player::PlayerID};
 
 use super::tank::material::TankMaterial;
 
@@ -42,6 +49,8 @@ pub struct Map{
 pub enum CurrentMap{
     None,
     AssetPath(String),
+    Generated{ width: usize, height: usize, seed: String },
+    Playlist{ queue: Vec<String>, index: usize },
     Handle(Handle<Map>)
 }
 
@@ -51,25 +60,166 @@ impl Default for CurrentMap {
     }
 }
 
+/// A reproducible pseudo-random number generator used for every random map
+/// decision (folder selection, spawn placement and procedural layout).
+///
+/// Seeding this from a single user-supplied string makes a whole match
+/// reproducible, which is required for replays, deterministic networked play,
+/// and reproducing a bad generated layout.
+///
+/// # Fields
+/// - `0`: The seeded PRNG backing every draw in this module.
+#[derive(Debug, Clone, Resource)]
+pub struct MapRng(pub Pcg64);
+
 pub const WALL_SIZE: f32 = 32.;
 
 /// A component representing a wall in the game.
 #[derive(Debug, Clone, Copy, Component)]
 pub struct Wall;
 
+/// Marks the 2D camera spawned for a level so it can be despawned on the next
+/// level transition rather than leaking one camera per map.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct MapCamera;
+
+/// The number of players a map is generated for, driving how many spawns the
+/// k-center seeding selects.
+///
+/// # Fields
+/// - `0`: The player count.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct PlayerCount(pub usize);
+
+/// Persistent progress through a map playlist.
+///
+/// The playlist survives the load-to-generate swap that turns [`CurrentMap`]
+/// into a handle, so [`next_level`] can advance to the following arena when a
+/// round ends.
+///
+/// # Fields
+/// - `queue`: The ordered asset paths, relative to `assets/maps`.
+/// - `index`: The currently active entry in `queue`.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct PlaylistProgress {
+    pub queue: Vec<String>,
+    pub index: usize,
+}
+
+/// The squared Euclidean distance between two grid coordinates.
+fn sq_dist(a: Coord, b: Coord) -> isize {
+    (a.0 as isize - b.0 as isize).pow(2) + (a.1 as isize - b.1 as isize).pow(2)
+}
+
+/// Selects `count` mutually spread-out spawn points from `map.spawn_points`
+/// using the Gonzalez greedy k-center 2-approximation.
+///
+/// The first point is drawn from the seeded RNG; each subsequent point is the
+/// candidate whose minimum squared distance to the already-chosen set is
+/// largest. A running `best_dist` over all candidates keeps every step
+/// `O(points)`. Returns `None` (after a warning) when `count` exceeds the
+/// available [`MAX_PLAYERS`] slots, or when the map offers fewer spawn points
+/// than players, so the caller can abort gracefully.
+fn select_spawns(map: &Map, count: usize, rng: &mut MapRng) -> Option<Vec<Coord>> {
+    if count > MAX_PLAYERS {
+        warn!(
+            "{} players were requested but only {} PlayerID slots exist",
+            count, MAX_PLAYERS
+        );
+        return None;
+    }
+    if map.spawn_points.len() < count {
+        warn!(
+            "Map has {} spawn points but {} players were requested",
+            map.spawn_points.len(), count
+        );
+        return None;
+    }
+    if count == 0 {
+        return Some(Vec::new());
+    }
+
+    let points = &map.spawn_points;
+    let first = rng.0.gen::<usize>() % points.len();
+
+    let mut chosen = Vec::with_capacity(count);
+    chosen.push(points[first]);
+
+    let mut best_dist: Vec<isize> = points.iter()
+        .map(|&p| sq_dist(p, points[first]))
+        .collect();
+
+    while chosen.len() < count {
+        let next = best_dist.iter()
+            .enumerate()
+            .max_by_key(|(_, dist)| **dist)
+            .map(|(i, _)| i)
+            .unwrap();
+
+        chosen.push(points[next]);
+
+        for (i, &p) in points.iter().enumerate() {
+            best_dist[i] = best_dist[i].min(sq_dist(p, points[next]));
+        }
+    }
+
+    Some(chosen)
+}
+
+/// The colour a tank is tinted by its selection order, cycling once exhausted.
+fn player_color(index: usize) -> LinearRgba {
+    match index % 4 {
+        0 => LinearRgba::new(1., 0., 0., 1.),
+        1 => LinearRgba::new(0., 0., 1., 1.),
+        2 => LinearRgba::new(0., 1., 0., 1.),
+        _ => LinearRgba::new(1., 1., 0., 1.),
+    }
+}
+
+/// The number of const-generic [`PlayerID`] slots the engine supports. Spawning
+/// more tanks than this would leave them without a controllable `PlayerID`, so
+/// generation aborts gracefully above this count.
+pub const MAX_PLAYERS: usize = 4;
+
+/// Tags a freshly spawned tank with its const-generic [`PlayerID`] by selection
+/// order. Callers guarantee `index < MAX_PLAYERS`; an out-of-range index warns
+/// rather than silently dropping the tag.
+fn insert_player_id(commands: &mut Commands, entity: bevy::prelude::Entity, index: usize) {
+    match index {
+        0 => { commands.entity(entity).insert(PlayerID::<0>); },
+        1 => { commands.entity(entity).insert(PlayerID::<1>); },
+        2 => { commands.entity(entity).insert(PlayerID::<2>); },
+        3 => { commands.entity(entity).insert(PlayerID::<3>); },
+        _ => warn!("No PlayerID slot for player index {index}"),
+    }
+}
+
 /// Loads the specified map from the asset server and sets it as the current map.
 /// 
 /// # Parameters
+/// - `commands`: Command queue used to spawn/insert entities for the loaded map.
 /// - `asset_server`: The asset server resource for loading map assets.
 /// - `current_map`: The current map resource to store the loaded map.
+/// - `map_rng`: The seeded [`MapRng`] resource driving reproducible folder selection.
 /// - `next_state`: A mutable reference to the next state in the game state management.
 pub fn load_map(
+    mut commands: Commands,
     asset_server: Res<AssetServer>,
 
     mut current_map: ResMut<CurrentMap>,
+    mut map_rng: ResMut<MapRng>,
     mut next_state: ResMut<NextState<Step>>
 ){
     let current_map = current_map.as_mut();
+
+    // Procedural maps carry no asset to resolve; hand off to the dedicated
+    // generation step, which fills an `Assets<Map>` entry and swaps in its
+    // handle before the shared `generate_map` path runs.
+    if let CurrentMap::Generated{..} = current_map {
+        next_state.set(Step::GenerateProcedural);
+        return;
+    }
+
     let mut selected_map = match &current_map {
         CurrentMap::None => {
             let map_folder: Vec<String> = fs::read_dir("assets/maps")
@@ -80,7 +230,7 @@ pub fn load_map(
                 .map(|file| format!("{}", file.file_name().to_str().unwrap()))
                 .collect();
 
-            let i: usize = rand::random::<usize>();
+            let i: usize = map_rng.0.gen::<usize>();
 
             CurrentMap::Handle(
                 asset_server.load(
@@ -100,9 +250,30 @@ pub fn load_map(
                 )
             )
         },
+        CurrentMap::Playlist{ queue, index } => {
+            let map_name = &queue[*index];
+            if !Path::new(&format!("assets/maps/{}", map_name)).exists() {
+                warn!("Map does not exist ({})", map_name);
+                panic!();
+            }
+
+            // Mirror the playlist into a persistent resource so `next_level` can
+            // advance it after this entry is swapped out for its handle below.
+            commands.insert_resource(PlaylistProgress{
+                queue: queue.clone(),
+                index: *index,
+            });
+
+            CurrentMap::Handle(
+                asset_server.load(
+                    format!("maps/{}", map_name)
+                )
+            )
+        },
+        CurrentMap::Generated{..} => unreachable!("handled above"),
         CurrentMap::Handle(_) => panic!("Invalid state when loading a map"),
     };
-    
+
     mem::swap(current_map, &mut selected_map);
 
     next_state.set(Step::GenerateMap);
@@ -120,7 +291,9 @@ pub fn generate_minimal_map(
     
     current_map: Res<CurrentMap>,
     maps: Res<Assets<Map>>,
-    
+    mut map_rng: ResMut<MapRng>,
+    player_count: Res<PlayerCount>,
+
     mut next_state: ResMut<NextState<Step>>
 ){
     let current_map = current_map.as_ref();
@@ -157,47 +330,19 @@ pub fn generate_minimal_map(
         walls
     );
 
-    let p1_spawn = {
-        let i1 = rand::random::<usize>() % map.spawn_points.len();
-
-        &map.spawn_points[i1]
+    let Some(spawns) = select_spawns(map, player_count.0, &mut map_rng) else {
+        next_state.set(Step::Finished);
+        return;
     };
 
-    let p2_spawn = map.spawn_points
-        .iter()
-        .filter(|&&point| point != *p1_spawn)
-        .fold(
-            (*p1_spawn, 0isize),
-            |acc, next| {
-                let dist = (next.0 as isize - p1_spawn.0 as isize).pow(2) + (next.1 as isize - p1_spawn.1 as isize).pow(2);
-                
-                match dist > acc.1 {
-                    true => (*next, dist),
-                    false => acc
-                }
-            }
-        ).0;
-
-    {
-        let p1 = create_minimal_tank(
-            p1_spawn.0 as f32 * WALL_SIZE,
-            p1_spawn.1 as f32 * WALL_SIZE,
-            0,
+    for (index, spawn) in spawns.iter().enumerate() {
+        let tank = create_minimal_tank(
+            spawn.0 as f32 * WALL_SIZE,
+            spawn.1 as f32 * WALL_SIZE,
+            index,
             &mut commands
         );
-        commands.entity(p1)
-            .insert(PlayerID::<0>);
-    }
-
-    {
-        let p2 = create_minimal_tank(
-            p2_spawn.0 as f32 * WALL_SIZE,
-            p2_spawn.1 as f32 * WALL_SIZE,
-            1,
-            &mut commands
-        );
-        commands.entity(p2)
-            .insert(PlayerID::<1>);
+        insert_player_id(&mut commands, tank, index);
     }
 
     next_state.set(Step::Finished);
@@ -220,7 +365,9 @@ pub fn generate_map(
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<TankMaterial>>,
-    
+    mut map_rng: ResMut<MapRng>,
+    player_count: Res<PlayerCount>,
+
     mut next_state: ResMut<NextState<Step>>
 ){
     let current_map = current_map.as_ref();
@@ -234,46 +381,53 @@ pub fn generate_map(
     };
 
     // println!("{map:?}");
-    // generate walls & pick spawn points
-    type WallBundle = (
-        Wall,
-        Transform,
-        GlobalTransform,
-
-        Collider,
-
-        Sprite,
-        Handle<Image>,
-        Visibility,
-        InheritedVisibility,
-        ViewVisibility,
-    );
-    let walls: Vec<WallBundle> = map.walls.iter()
-        .map(|(x, y)| (*x as f32, *y as f32))
-        .map( |(x, y)| (
+    // Render every wall cell as a tile in a single chunked tilemap rather than
+    // one sprite entity per cell, so thousands of walls collapse into one draw
+    // and the wall texture is only loaded once.
+    let map_size = TilemapSize{ x: map.dim.0 as u32, y: map.dim.1 as u32 };
+    let mut tile_storage = TileStorage::empty(map_size);
+    let tilemap_entity = commands.spawn_empty().id();
+
+    for (x, y) in map.walls.iter().map(|(x, y)| (*x as u32, *y as u32)) {
+        let tile_pos = TilePos{ x, y };
+        let tile_entity = commands.spawn(TileBundle{
+            position: tile_pos,
+            tilemap_id: TilemapId(tilemap_entity),
+            texture_index: TileTextureIndex(0),
+            ..Default::default()
+        }).id();
+        tile_storage.set(&tile_pos, tile_entity);
+
+        // Colliders stay one lightweight collider-only entity per cell so the
+        // physics shape is unchanged from the old per-sprite approach.
+        commands.spawn((
             Wall,
             Transform{
                 translation: Vec3{
-                    x: x * WALL_SIZE,
-                    y: y * WALL_SIZE,
+                    x: x as f32 * WALL_SIZE,
+                    y: y as f32 * WALL_SIZE,
                     z: 0.,
                 },
                 ..Default::default()
             },
-            Default::default(),
-
+            GlobalTransform::default(),
             Collider::cuboid(WALL_SIZE/2., WALL_SIZE/2.),
-            
-            Default::default(),
-            asset_server.load("textures\\map\\wall.png"),
-            Default::default(),
-            Default::default(),
-            Default::default()
-        ))
-        .collect();
-    commands.spawn_batch(
-        walls
-    );
+        ));
+    }
+
+    let tile_size = TilemapTileSize{ x: WALL_SIZE, y: WALL_SIZE };
+    commands.entity(tilemap_entity).insert(TilemapBundle{
+        grid_size: tile_size.into(),
+        map_type: TilemapType::Square,
+        size: map_size,
+        storage: tile_storage,
+        texture: TilemapTexture::Single(asset_server.load("textures\\map\\wall.png")),
+        tile_size,
+        // Tile centers sit at `coord * WALL_SIZE` from the tilemap origin, so an
+        // untranslated tilemap aligns them with the collider positions.
+        transform: Transform::default(),
+        ..Default::default()
+    });
 
     commands.spawn((
         Camera2dBundle{
@@ -287,83 +441,281 @@ pub fn generate_map(
             },
             ..Default::default()
         },
+        MapCamera,
     ));
 
-    
-    let p1_spawn = {
-        let i1 = rand::random::<usize>() % map.spawn_points.len();
 
-        &map.spawn_points[i1]
+    let Some(spawns) = select_spawns(map, player_count.0, &mut map_rng) else {
+        next_state.set(Step::Finished);
+        return;
     };
 
-    let p2_spawn = map.spawn_points
-        .iter()
-        .filter(|&&point| point != *p1_spawn)
-        .fold(
-            (*p1_spawn, 0isize),
-            |acc, next| {
-                let dist = (next.0 as isize - p1_spawn.0 as isize).pow(2) + (next.1 as isize - p1_spawn.1 as isize).pow(2);
-                
-                match dist > acc.1 {
-                    true => (*next, dist),
-                    false => acc
-                }
-            }
-        ).0;
-    {
-        let p1 = create_tank(
-            p1_spawn.0 as f32 * WALL_SIZE,
-            p1_spawn.1 as f32 * WALL_SIZE,
-            0,
-            LinearRgba::new(1., 0., 0., 1.),
+    for (index, spawn) in spawns.iter().enumerate() {
+        let tank = create_tank(
+            spawn.0 as f32 * WALL_SIZE,
+            spawn.1 as f32 * WALL_SIZE,
+            index,
+            player_color(index),
             &mut commands,
             &mut meshes,
             &mut materials,
             &asset_server
         );
-        commands.entity(p1)
-            .insert(PlayerID::<0>);
+        insert_player_id(&mut commands, tank, index);
     }
 
-    {
-        let p2 = create_tank(
-            p2_spawn.0 as f32 * WALL_SIZE,
-            p2_spawn.1 as f32 * WALL_SIZE,
-            1,
-            LinearRgba::new(0., 0., 1., 1.),
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-            &asset_server
-        );
-        commands.entity(p2)
-            .insert(PlayerID::<1>);
+    next_state.set(Step::Finished);
+}
+
+/// The number of rooms the procedural generator tries to fit onto a map.
+const PROC_ROOM_COUNT: usize = 8;
+/// How many rejection-sampling attempts are made before giving up on rooms.
+const PROC_ROOM_ATTEMPTS: usize = 128;
+/// The inclusive bounds on a procedurally placed room's side length.
+const PROC_ROOM_MIN: usize = 3;
+const PROC_ROOM_MAX: usize = 8;
+/// The fraction of leftover open cells turned into interior pillar walls.
+const PROC_PILLAR_DENSITY: f64 = 0.05;
+
+/// Builds a `Map` procedurally with a room-and-corridor algorithm and feeds it
+/// into `Assets<Map>` so the existing `generate_map` path consumes it unchanged.
+///
+/// Every random choice is drawn from [`MapRng`], re-seeded from the
+/// [`CurrentMap::Generated`] seed, so a given `(width, height, seed)` always
+/// yields the same layout.
+///
+/// # Parameters
+/// - `current_map`: The current map resource, read for generation parameters and updated with the resulting handle.
+/// - `maps`: The asset store the generated map is inserted into.
+/// - `map_rng`: The seeded PRNG driving room, corridor and pillar placement.
+/// - `next_state`: A mutable reference to the next state in the game state management.
+pub fn generate_procedural_map(
+    mut current_map: ResMut<CurrentMap>,
+    mut maps: ResMut<Assets<Map>>,
+    mut map_rng: ResMut<MapRng>,
+    mut next_state: ResMut<NextState<Step>>
+){
+    let CurrentMap::Generated{ width, height, seed } = current_map.as_ref() else {
+        panic!("Invalid state when generating a procedural map");
+    };
+    let (width, height) = (*width, *height);
+
+    // The generated seed is authoritative for this map, so the whole layout is
+    // reproducible from it alone.
+    map_rng.0 = Seeder::from(seed.clone()).make_rng();
+
+    // `walls[x][y]` is `true` for a solid cell. Start with a solid border and
+    // an open interior, matching how the file-loaded maps are shaped.
+    let mut walls = vec![vec![false; height]; width];
+    for col in walls.iter_mut() {
+        col[0] = true;
+        col[height - 1] = true;
+    }
+    for y in 0..height {
+        walls[0][y] = true;
+        walls[width - 1][y] = true;
     }
 
+    // Cells carved out as part of a room or corridor; kept out of the pillar
+    // scatter so traversable space stays clear.
+    let mut carved = vec![vec![false; height]; width];
 
-    next_state.set(Step::Finished);
+    let carve = |walls: &mut Vec<Vec<bool>>, carved: &mut Vec<Vec<bool>>, x: usize, y: usize| {
+        walls[x][y] = false;
+        carved[x][y] = true;
+    };
+
+    // Place non-overlapping rooms by rejection sampling, rejecting any room
+    // whose bounding box (plus a one-cell margin) meets an existing room.
+    let mut rooms: Vec<(usize, usize, usize, usize)> = Vec::new();
+    for _ in 0..PROC_ROOM_ATTEMPTS {
+        if rooms.len() >= PROC_ROOM_COUNT {
+            break;
+        }
+
+        let w = map_rng.0.gen_range(PROC_ROOM_MIN..=PROC_ROOM_MAX);
+        let h = map_rng.0.gen_range(PROC_ROOM_MIN..=PROC_ROOM_MAX);
+        if w + 2 >= width || h + 2 >= height {
+            continue;
+        }
+
+        let x = map_rng.0.gen_range(1..width - w - 1);
+        let y = map_rng.0.gen_range(1..height - h - 1);
+
+        let overlaps = rooms.iter().any(|&(rx, ry, rw, rh)| {
+            x <= rx + rw + 1 && rx <= x + w + 1 && y <= ry + rh + 1 && ry <= y + h + 1
+        });
+        if overlaps {
+            continue;
+        }
+
+        for cx in x..x + w {
+            for cy in y..y + h {
+                carve(&mut walls, &mut carved, cx, cy);
+            }
+        }
+        rooms.push((x, y, w, h));
+    }
+
+    // Connect successive room centers with L-shaped corridors (a horizontal run
+    // then a vertical run).
+    let centers: Vec<Coord> = rooms.iter()
+        .map(|&(x, y, w, h)| (x + w / 2, y + h / 2))
+        .collect();
+    for pair in centers.windows(2) {
+        let (x0, y0) = pair[0];
+        let (x1, y1) = pair[1];
+
+        for x in x0.min(x1)..=x0.max(x1) {
+            carve(&mut walls, &mut carved, x, y0);
+        }
+        for y in y0.min(y1)..=y0.max(y1) {
+            carve(&mut walls, &mut carved, x1, y);
+        }
+    }
+
+    // Scatter pillars through the open cells left outside rooms and corridors.
+    for x in 1..width - 1 {
+        for y in 1..height - 1 {
+            if !carved[x][y] && !walls[x][y] && map_rng.0.gen_bool(PROC_PILLAR_DENSITY) {
+                walls[x][y] = true;
+            }
+        }
+    }
+
+    let mut wall_coords = Vec::new();
+    for (x, col) in walls.iter().enumerate() {
+        for (y, &solid) in col.iter().enumerate() {
+            if solid {
+                wall_coords.push((x, y));
+            }
+        }
+    }
+
+    let handle = maps.add(Map{
+        dim: (width, height),
+        walls: wall_coords,
+        spawn_points: centers,
+    });
+
+    *current_map.as_mut() = CurrentMap::Handle(handle);
+
+    next_state.set(Step::GenerateMap);
+}
+
+/// Ends the current round and advances the playlist: despawns every wall and
+/// tank, steps the [`PlaylistProgress`] index (cycling at the end), and
+/// re-enters [`Step::LoadMap`] with the next arena.
+///
+/// # Parameters
+/// - `commands`: The command buffer used to despawn the old arena.
+/// - `walls`: Every collider wall entity to tear down.
+/// - `tilemaps`: The batched wall tilemaps, despawned alongside their tiles.
+/// - `tanks`: Every tank entity to tear down.
+/// - `cameras`: The per-level [`MapCamera`] entity to tear down.
+/// - `progress`: The playlist progress to advance.
+/// - `current_map`: The current map resource, reset to the next playlist entry.
+/// - `next_state`: A mutable reference to the next state in the game state management.
+pub fn next_level(
+    mut commands: Commands,
+
+    walls: Query<Entity, With<Wall>>,
+    tilemaps: Query<(Entity, &TileStorage)>,
+    tanks: Query<Entity, With<Tank>>,
+    cameras: Query<Entity, With<MapCamera>>,
+
+    mut progress: ResMut<PlaylistProgress>,
+    mut current_map: ResMut<CurrentMap>,
+    mut next_state: ResMut<NextState<Step>>
+){
+    if progress.queue.is_empty() {
+        warn!("NextLevel requested without an active playlist");
+        return;
+    }
+
+    for entity in &walls {
+        commands.entity(entity).despawn();
+    }
+    for (tilemap, storage) in &tilemaps {
+        for tile in storage.iter().flatten() {
+            commands.entity(*tile).despawn();
+        }
+        commands.entity(tilemap).despawn();
+    }
+    for entity in &tanks {
+        commands.entity(entity).despawn_recursive();
+    }
+    for entity in &cameras {
+        commands.entity(entity).despawn();
+    }
+
+    progress.index = (progress.index + 1) % progress.queue.len();
+
+    *current_map = CurrentMap::Playlist{
+        queue: progress.queue.clone(),
+        index: progress.index,
+    };
+
+    next_state.set(Step::LoadMap);
+}
+
+/// Selects which arena(s) [`MapPlugin`] plays. The variants are mutually
+/// exclusive, so exactly one source is chosen at the construction site.
+///
+/// # Variants
+/// - `Random`: Pick a random map file from `assets/maps`.
+/// - `Asset`: Load a single named map file under `assets/maps`.
+/// - `Playlist`: Walk through an ordered playlist of map files.
+/// - `Generated`: Procedurally generate a map of the given dimensions and seed.
+#[derive(Debug, Clone, Default)]
+pub enum MapSource {
+    #[default]
+    Random,
+    Asset(String),
+    Playlist(Vec<String>),
+    Generated{ width: usize, height: usize, seed: String },
 }
 
 /// A Bevy plugin for managing map loading and generation.
-/// 
+///
 /// # Fields
-/// - `bool`: A flag indicating whether to generate a minimal map(headless) or a complete map.
-pub struct MapPlugin(pub bool, pub Option<String>);
+/// - `complete`: Generate a full map (`true`) or a minimal headless map (`false`).
+/// - `seed`: The seed string driving every random map decision.
+/// - `player_count`: The number of players to place spawns for.
+/// - `source`: Which arena(s) to play; see [`MapSource`].
+pub struct MapPlugin {
+    pub complete: bool,
+    pub seed: String,
+    pub player_count: usize,
+    pub source: MapSource,
+}
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app
+            .add_plugins(TilemapPlugin)
+
             .init_asset::<Map>()
             .init_asset_loader::<MapLoader>()
+            .init_asset_loader::<LdtkLoader>()
 
+            .insert_resource(MapRng(Seeder::from(self.seed.clone()).make_rng()))
+            .insert_resource(PlayerCount(self.player_count))
+            .init_resource::<PlaylistProgress>()
 
             .init_state::<Step>();
 
-            match &self.1{
-                Some(selected_map) => {
+            match &self.source {
+                MapSource::Generated{ width, height, seed } => {
+                    app.insert_resource(CurrentMap::Generated{ width: *width, height: *height, seed: seed.clone() });
+                },
+                MapSource::Playlist(queue) => {
+                    app.insert_resource(CurrentMap::Playlist{ queue: queue.clone(), index: 0 });
+                },
+                MapSource::Asset(selected_map) => {
                     app.insert_resource(CurrentMap::AssetPath(selected_map.clone()));
                 },
-                None => {
+                MapSource::Random => {
                     app.init_resource::<CurrentMap>();
                 },
             };
@@ -372,8 +724,18 @@ impl Plugin for MapPlugin {
                     Startup,
                     load_map.run_if(in_state(Step::LoadMap))
                 );
-            
-        match self.0 {
+
+            app.add_systems(
+                    Update,
+                    (
+                        // `load_map` also runs here so `NextLevel` can re-enter it.
+                        load_map.run_if(in_state(Step::LoadMap)),
+                        generate_procedural_map.run_if(in_state(Step::GenerateProcedural)),
+                        next_level.run_if(in_state(Step::NextLevel)),
+                    )
+                );
+
+        match self.complete {
             false => {
                 app.add_systems(
                     Update,
@@ -392,4 +754,56 @@ impl Plugin for MapPlugin {
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_seeder::Seeder;
+
+    fn rng(seed: &str) -> MapRng {
+        MapRng(Seeder::from(seed.to_string()).make_rng())
+    }
+
+    fn map_with(spawn_points: Vec<Coord>) -> Map {
+        Map { dim: (0, 0), walls: Vec::new(), spawn_points }
+    }
+
+    #[test]
+    fn select_spawns_returns_distinct_spread_points() {
+        let map = map_with(vec![(0, 0), (0, 10), (10, 0), (10, 10), (5, 5)]);
+        let chosen = select_spawns(&map, 3, &mut rng("spread")).expect("enough points");
+
+        assert_eq!(chosen.len(), 3);
+        for c in &chosen {
+            assert!(map.spawn_points.contains(c));
+        }
+
+        let mut unique = chosen.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), chosen.len(), "spawns must be mutually distinct");
+    }
+
+    #[test]
+    fn select_spawns_covers_every_point_when_count_matches() {
+        let points = vec![(0, 0), (4, 0), (0, 4)];
+        let mut chosen = select_spawns(&map_with(points.clone()), points.len(), &mut rng("all"))
+            .expect("enough points");
+        chosen.sort_unstable();
+
+        let mut expected = points;
+        expected.sort_unstable();
+        assert_eq!(chosen, expected);
+    }
+
+    #[test]
+    fn select_spawns_aborts_when_too_few_points() {
+        assert!(select_spawns(&map_with(vec![(0, 0)]), 2, &mut rng("few")).is_none());
+    }
+
+    #[test]
+    fn select_spawns_aborts_above_max_players() {
+        let points = (0..10).map(|i| (i, 0)).collect();
+        assert!(select_spawns(&map_with(points), MAX_PLAYERS + 1, &mut rng("many")).is_none());
+    }
+}